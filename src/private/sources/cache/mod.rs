@@ -0,0 +1,225 @@
+//! An expiring, self-refreshing cache used to avoid re-querying Amazon Verified Permissions for
+//! every request while still picking up changes made to the underlying policy store.
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::{Mutex, Notify, RwLock};
+
+/// Fraction of the TTL after which an entry is proactively refreshed instead of waiting for it
+/// to expire outright. Mirrors the refresh-ahead window used by the endpoint discovery cache.
+const REFRESH_AHEAD_RATIO: f64 = 0.8;
+
+/// A single cached value together with the instants at which it should be refreshed and expired.
+struct Entry<V> {
+    value: V,
+    refresh_at: Instant,
+    expires_at: Instant,
+}
+
+impl<V> Entry<V> {
+    fn is_expired(&self, now: Instant) -> bool {
+        now >= self.expires_at
+    }
+
+    fn needs_refresh(&self, now: Instant) -> bool {
+        now >= self.refresh_at
+    }
+}
+
+/// An expiring cache that wraps each value with a TTL and de-duplicates concurrent loads.
+///
+/// `get` never returns an expired value. `get_or_load` additionally triggers a refresh when an
+/// entry is missing, expired, or within the refresh-ahead window (80% of the TTL), and ensures
+/// only one load is ever in flight per key: concurrent callers for the same key await the single
+/// outstanding load rather than each issuing their own AVP call.
+///
+/// This intentionally does not implement the [`super::Cache`] trait: `Cache` is documented as
+/// non-thread-safe by design (`get` hands back a bare `&Value` so it cannot guard its state with
+/// a lock), whereas the single-in-flight-load guarantee here requires interior synchronization
+/// and an async, owned-`Value` API. `ExpiringCache` is a standalone sibling for callers that need
+/// expiry and refresh-ahead semantics, not a thread-safe drop-in implementor of `Cache`.
+pub struct ExpiringCache<K, V> {
+    ttl: Duration,
+    entries: RwLock<HashMap<K, Entry<V>>>,
+    in_flight: Mutex<HashMap<K, Arc<Notify>>>,
+}
+
+impl<K, V> ExpiringCache<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    /// Creates a new cache where each inserted value expires `ttl` after being loaded.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cached value for `key`, or `None` if it is missing or expired.
+    pub async fn get(&self, key: &K) -> Option<V> {
+        let now = Instant::now();
+        self.entries
+            .read()
+            .await
+            .get(key)
+            .filter(|entry| !entry.is_expired(now))
+            .map(|entry| entry.value.clone())
+    }
+
+    /// Inserts `value` for `key`, resetting its TTL and refresh-ahead window.
+    pub async fn put(&self, key: K, value: V) {
+        let now = Instant::now();
+        let entry = Entry {
+            value,
+            refresh_at: now + self.ttl.mul_f64(REFRESH_AHEAD_RATIO),
+            expires_at: now + self.ttl,
+        };
+        self.entries.write().await.insert(key, entry);
+    }
+
+    /// Returns the cached value for `key`, transparently loading (and caching) it via `loader`
+    /// when the entry is missing, expired, or within the refresh-ahead window.
+    ///
+    /// At most one load per key is ever in flight: the first caller to observe a miss becomes the
+    /// leader, drives the load, and wakes every other waiter once it completes. A follower that
+    /// wakes up to find the leader didn't leave behind a usable value (the load failed, or the
+    /// entry is already past hard expiry again) re-enters leader election rather than issuing its
+    /// own load, so a thundering herd never forms even when loads keep failing.
+    pub async fn get_or_load<F, Fut, E>(&self, key: &K, loader: F) -> Result<V, E>
+    where
+        F: Fn(K) -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        loop {
+            let now = Instant::now();
+            if let Some(entry) = self.entries.read().await.get(key) {
+                if !entry.needs_refresh(now) {
+                    return Ok(entry.value.clone());
+                }
+            }
+
+            let mut in_flight = self.in_flight.lock().await;
+
+            // Someone else is already loading this key: wait for them instead of issuing a
+            // second AVP call, then serve whatever they left behind if it is still fresh. The
+            // `Notified` future is created before the lock is released, so the leader's wake-up
+            // cannot slip in between and be missed.
+            if let Some(notify) = in_flight.get(key).cloned() {
+                let notified = notify.notified();
+                drop(in_flight);
+                notified.await;
+                if let Some(value) = self.get(key).await {
+                    return Ok(value);
+                }
+                // The leader's load left nothing usable (it failed, or the entry has already
+                // expired again) — retry leader election instead of falling through to our own
+                // load, which would defeat the single-in-flight-load guarantee.
+                continue;
+            }
+            in_flight.insert(key.clone(), Arc::new(Notify::new()));
+            drop(in_flight);
+
+            let result = loader(key.clone()).await;
+            if let Ok(value) = &result {
+                self.put(key.clone(), value.clone()).await;
+            }
+            if let Some(notify) = self.in_flight.lock().await.remove(key) {
+                notify.notify_waiters();
+            }
+
+            return result;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::ExpiringCache;
+
+    #[tokio::test]
+    async fn get_returns_none_once_expired() {
+        let cache = ExpiringCache::new(Duration::from_millis(10));
+        cache.put("key", 1).await;
+        assert_eq!(cache.get(&"key").await, Some(1));
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(cache.get(&"key").await, None);
+    }
+
+    #[tokio::test]
+    async fn get_or_load_dedupes_concurrent_loads_for_the_same_key() {
+        let cache = Arc::new(ExpiringCache::new(Duration::from_secs(60)));
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let mut handles = Vec::new();
+        for _ in 0..8 {
+            let cache = Arc::clone(&cache);
+            let calls = Arc::clone(&calls);
+            handles.push(tokio::spawn(async move {
+                cache
+                    .get_or_load(&"key", |key| {
+                        let calls = Arc::clone(&calls);
+                        async move {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(20)).await;
+                            Ok::<_, ()>(format!("value-for-{key}"))
+                        }
+                    })
+                    .await
+            }));
+        }
+
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), Ok("value-for-key".to_string()));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn get_or_load_never_runs_two_loads_at_once_when_the_leader_fails() {
+        let cache = Arc::new(ExpiringCache::<&str, u32>::new(Duration::from_secs(60)));
+        let concurrent = Arc::new(AtomicUsize::new(0));
+        let max_concurrent = Arc::new(AtomicUsize::new(0));
+
+        let mut handles = Vec::new();
+        for _ in 0..8 {
+            let cache = Arc::clone(&cache);
+            let concurrent = Arc::clone(&concurrent);
+            let max_concurrent = Arc::clone(&max_concurrent);
+            handles.push(tokio::spawn(async move {
+                cache
+                    .get_or_load(&"key", |_key| {
+                        let concurrent = Arc::clone(&concurrent);
+                        let max_concurrent = Arc::clone(&max_concurrent);
+                        async move {
+                            let now = concurrent.fetch_add(1, Ordering::SeqCst) + 1;
+                            max_concurrent.fetch_max(now, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(10)).await;
+                            concurrent.fetch_sub(1, Ordering::SeqCst);
+                            Err::<u32, &str>("throttled")
+                        }
+                    })
+                    .await
+            }));
+        }
+
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), Err("throttled"));
+        }
+
+        // Before the fix, every follower that woke up to a still-missing value launched its own
+        // load instead of retrying leader election, so all 8 would overlap. With the fix there is
+        // never more than one load in flight at a time, even though every single one fails.
+        assert_eq!(max_concurrent.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,119 @@
+//! A builder for the AVP `Client`, for callers that need to control identity/credential caching,
+//! inject a custom async sleep, or register request interceptors.
+use aws_sdk_verifiedpermissions::config::{Credentials, IdentityCache, Region};
+use aws_sdk_verifiedpermissions::{Client, Config};
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, TokioSleep};
+
+use super::interceptor::{RequestInterceptor, RequestInterceptors};
+
+/// Builds an AVP `Client`, defaulting to identity caching on and a Tokio-backed sleep so
+/// existing behavior is unchanged for callers that don't need to customize either.
+pub struct AvpClientConfig {
+    identity_cache: IdentityCache,
+    sleep_impl: SharedAsyncSleep,
+    interceptors: Vec<Box<dyn RequestInterceptor>>,
+}
+
+impl AvpClientConfig {
+    /// Creates a config with identity caching enabled and a Tokio sleep, matching the behavior
+    /// callers got before this builder existed.
+    pub fn new() -> Self {
+        Self {
+            identity_cache: IdentityCache::lazy().build(),
+            sleep_impl: SharedAsyncSleep::new(TokioSleep::new()),
+            interceptors: Vec::new(),
+        }
+    }
+
+    /// Disables identity/credential caching entirely, for callers that rotate credentials
+    /// externally and don't want the SDK to cache a stale identity between rotations.
+    pub fn without_identity_cache(mut self) -> Self {
+        self.identity_cache = IdentityCache::no_cache();
+        self
+    }
+
+    /// Overrides the identity cache configuration directly.
+    pub fn identity_cache(mut self, identity_cache: IdentityCache) -> Self {
+        self.identity_cache = identity_cache;
+        self
+    }
+
+    /// Supplies a custom [`AsyncSleep`], letting tests drive a mock clock instead of a real one.
+    pub fn sleep_impl(mut self, sleep_impl: impl AsyncSleep + 'static) -> Self {
+        self.sleep_impl = SharedAsyncSleep::new(sleep_impl);
+        self
+    }
+
+    /// Registers a [`RequestInterceptor`] to run before every `Load`/`Read` call made through
+    /// the built client.
+    pub fn with_interceptor(mut self, interceptor: impl RequestInterceptor + 'static) -> Self {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+
+    /// Builds the AVP `Client` for `credentials_provider` and `region`, wiring through the
+    /// identity cache, sleep implementation, and any registered interceptors.
+    pub fn build(self, credentials_provider: Credentials, region: Region) -> Client {
+        let conf = Config::builder()
+            .credentials_provider(credentials_provider)
+            .region(region)
+            .identity_cache(self.identity_cache)
+            .sleep_impl(self.sleep_impl)
+            .interceptor(RequestInterceptors::new(self.interceptors))
+            .build();
+
+        Client::from_conf(conf)
+    }
+}
+
+impl Default for AvpClientConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aws_smithy_async::rt::sleep::TokioSleep;
+
+    use super::AvpClientConfig;
+    use super::super::interceptor::RequestInterceptor;
+
+    struct NoopInterceptor;
+
+    impl RequestInterceptor for NoopInterceptor {
+        fn modify_before_signing(&self, _headers: &mut http::HeaderMap) {}
+    }
+
+    #[test]
+    fn builder_methods_chain_and_build_a_client() {
+        let _client = AvpClientConfig::new()
+            .without_identity_cache()
+            .sleep_impl(TokioSleep::new())
+            .with_interceptor(NoopInterceptor)
+            .build(
+                aws_sdk_verifiedpermissions::config::Credentials::new(
+                    "a",
+                    "b",
+                    Some("c".to_string()),
+                    None,
+                    "d",
+                ),
+                aws_sdk_verifiedpermissions::config::Region::new("us-east-1"),
+            );
+    }
+
+    #[test]
+    fn default_matches_new() {
+        let _client = AvpClientConfig::default().build(
+            aws_sdk_verifiedpermissions::config::Credentials::new(
+                "a",
+                "b",
+                Some("c".to_string()),
+                None,
+                "d",
+            ),
+            aws_sdk_verifiedpermissions::config::Region::new("us-east-1"),
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! DVR (dynamic variable recording): capture real AVP traffic once and replay it deterministically
+//! in tests, rather than hand-building `RequestResponsePair` vectors for every test.
+mod recording_connector;
+mod replay_connector;
+
+pub use recording_connector::RecordingConnector;
+pub use replay_connector::ReplayConnector;
+
+use std::path::Path;
+
+use aws_credential_types::Credentials;
+use aws_sdk_verifiedpermissions::{Client, Config};
+use aws_types::region::Region;
+use serde::{Deserialize, Serialize};
+
+/// Request headers whose values vary from run to run (SDK user-agent strings, signatures,
+/// timestamps) and so are ignored when matching a replayed request against a recording.
+const VOLATILE_HEADERS: &[&str] = &["x-amz-user-agent", "authorization", "x-amz-date", "date"];
+
+/// One recorded request/response exchange, serialized to and from the recording file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RecordedEvent {
+    pub(crate) method: String,
+    pub(crate) uri: String,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) body: String,
+    pub(crate) status: u16,
+    pub(crate) response_body: String,
+}
+
+/// Builds a mock AVP `Client` whose traffic is replayed from `path`, a file previously produced
+/// by [`RecordingConnector`]. This allows deterministic offline tests to run against golden
+/// traffic captured once from a live AVP endpoint.
+///
+/// # Panics
+///
+/// Will panic if `path` cannot be read or does not contain a valid recording.
+pub fn build_client_from_recording(path: impl AsRef<Path>) -> Client {
+    let connector = ReplayConnector::load(path).expect("failed to load recording");
+
+    let conf = Config::builder()
+        .credentials_provider(Credentials::new("a", "b", Some("c".to_string()), None, "d"))
+        .region(Region::new("us-east-1"))
+        .http_connector(connector)
+        .build();
+
+    Client::from_conf(conf)
+}
+
+/// Whether `name` is a header whose value is expected to change between recording and replay.
+fn header_is_volatile(name: &str) -> bool {
+    VOLATILE_HEADERS
+        .iter()
+        .any(|volatile| volatile.eq_ignore_ascii_case(name))
+}
+
+/// Headers worth comparing when matching a replayed request against a recorded one, with
+/// volatile headers stripped out.
+fn stable_headers(headers: &[(String, String)]) -> Vec<(String, String)> {
+    let mut stable: Vec<(String, String)> = headers
+        .iter()
+        .filter(|(name, _)| !header_is_volatile(name))
+        .cloned()
+        .collect();
+    stable.sort();
+    stable
+}
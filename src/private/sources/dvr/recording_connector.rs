@@ -0,0 +1,163 @@
+//! A connector wrapper that captures every request/response pair flowing through a real AVP HTTP
+//! connector, so they can be replayed offline by a [`super::ReplayConnector`].
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use aws_smithy_client::erase::DynConnector;
+use aws_smithy_http::body::SdkBody;
+use http::{Request, Response};
+use tower::Service;
+
+use super::RecordedEvent;
+
+/// Wraps a real HTTP connector and records each request/response pair it carries in memory as
+/// they happen; [`Self::save`] writes them to `path`, so a golden-traffic capture only ever needs
+/// to be taken once against a live AVP endpoint.
+#[derive(Clone)]
+pub struct RecordingConnector {
+    inner: DynConnector,
+    path: PathBuf,
+    events: Arc<Mutex<Vec<RecordedEvent>>>,
+}
+
+impl RecordingConnector {
+    /// Wraps `inner`, recording every exchange that flows through it. Call [`Self::save`] once
+    /// the recording session is complete to persist the captured events to `path`.
+    pub fn new(inner: DynConnector, path: impl AsRef<Path>) -> Self {
+        Self {
+            inner,
+            path: path.as_ref().to_path_buf(),
+            events: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Writes every request/response pair recorded so far to the recording file as pretty JSON.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the events cannot be serialized or the file cannot be written.
+    pub fn save(&self) {
+        let events = self.events.lock().unwrap();
+        let json = serde_json::to_string_pretty(&*events).expect("failed to serialize recording");
+        std::fs::write(&self.path, json).expect("failed to write recording file");
+    }
+}
+
+impl Service<Request<SdkBody>> for RecordingConnector {
+    type Response = Response<SdkBody>;
+    type Error = <DynConnector as Service<Request<SdkBody>>>::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<SdkBody>) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let events = Arc::clone(&self.events);
+
+        let method = req.method().to_string();
+        let uri = req.uri().to_string();
+        let headers = req
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    value.to_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let body = String::from_utf8_lossy(req.body().bytes().unwrap_or_default()).to_string();
+
+        Box::pin(async move {
+            let response = inner.call(req).await?;
+            let status = response.status().as_u16();
+            let response_body =
+                String::from_utf8_lossy(response.body().bytes().unwrap_or_default()).to_string();
+
+            events.lock().unwrap().push(RecordedEvent {
+                method,
+                uri,
+                headers,
+                body,
+                status,
+                response_body,
+            });
+
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::Infallible;
+
+    use super::super::ReplayConnector;
+    use super::*;
+
+    /// A fixed-response connector standing in for a real AVP HTTP connector in tests.
+    #[derive(Clone)]
+    struct FixedResponse {
+        status: u16,
+        body: String,
+    }
+
+    impl Service<Request<SdkBody>> for FixedResponse {
+        type Response = Response<SdkBody>;
+        type Error = Infallible;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: Request<SdkBody>) -> Self::Future {
+            std::future::ready(Ok(Response::builder()
+                .status(self.status)
+                .body(SdkBody::from(self.body.clone()))
+                .unwrap()))
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("avp_dvr_test_{name}_{}.json", std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn records_a_call_and_the_recording_can_be_replayed() {
+        let path = temp_path("records_a_call_and_the_recording_can_be_replayed");
+        let inner = DynConnector::new(FixedResponse {
+            status: 200,
+            body: r#"{"policy":"a"}"#.to_string(),
+        });
+        let mut recorder = RecordingConnector::new(inner, &path);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .header("content-type", "application/x-amz-json-1.0")
+            .body(SdkBody::from(r#"{"policyStoreId":"a"}"#))
+            .unwrap();
+        let recorded = recorder.call(request).await.unwrap();
+        assert_eq!(recorded.status(), 200);
+        recorder.save();
+
+        let mut replay = ReplayConnector::load(&path).unwrap();
+        let replayed_request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .header("content-type", "application/x-amz-json-1.0")
+            .body(SdkBody::from(r#"{"policyStoreId":"a"}"#))
+            .unwrap();
+        let replayed = replay.call(replayed_request).await.unwrap();
+        let body = String::from_utf8_lossy(replayed.body().bytes().unwrap()).to_string();
+        assert_eq!(body, r#"{"policy":"a"}"#);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
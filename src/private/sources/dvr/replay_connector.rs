@@ -0,0 +1,190 @@
+//! A connector that deserializes a recording produced by [`super::RecordingConnector`] and
+//! replays its responses for matching requests, with no network traffic involved.
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use aws_smithy_http::body::SdkBody;
+use http::{Request, Response};
+use tower::Service;
+
+use super::{stable_headers, RecordedEvent};
+
+/// Errors that can occur while replaying a recorded AVP interaction.
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    /// No recorded request matched the incoming request.
+    #[error("no recorded event matches method {method} uri {uri}")]
+    NoMatch {
+        /// The HTTP method of the unmatched request.
+        method: String,
+        /// The URI of the unmatched request.
+        uri: String,
+    },
+}
+
+/// Replays a fixed sequence of request/response pairs previously captured by a
+/// [`super::RecordingConnector`], matching incoming requests ignoring volatile headers such as
+/// `x-amz-user-agent`, `authorization`, and dates.
+#[derive(Clone)]
+pub struct ReplayConnector {
+    events: Arc<Mutex<Vec<RecordedEvent>>>,
+}
+
+impl ReplayConnector {
+    /// Loads a recording previously written by [`super::RecordingConnector::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` cannot be read or does not contain valid JSON.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let events: Vec<RecordedEvent> = serde_json::from_str(&contents)?;
+        Ok(Self {
+            events: Arc::new(Mutex::new(events)),
+        })
+    }
+
+    #[cfg(test)]
+    fn from_events(events: Vec<RecordedEvent>) -> Self {
+        Self {
+            events: Arc::new(Mutex::new(events)),
+        }
+    }
+
+    /// Finds and removes the first recorded event whose method, URI, stable headers, and body
+    /// match the incoming request, returning its recorded response.
+    ///
+    /// AVP's JSON-RPC-style operations all hit the same URI with the same stable headers for a
+    /// given action (e.g. `ListPolicies`), differing only in the request body (policy store id,
+    /// `nextToken`, item id, etc.), so the body must be part of the match key or two recorded
+    /// calls to the same action become indistinguishable.
+    fn take_matching(&self, req: &Request<SdkBody>) -> Result<RecordedEvent, ReplayError> {
+        let method = req.method().to_string();
+        let uri = req.uri().to_string();
+        let headers = req
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    value.to_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let wanted_headers = stable_headers(&headers);
+        let body = String::from_utf8_lossy(req.body().bytes().unwrap_or_default()).to_string();
+
+        let mut events = self.events.lock().unwrap();
+        let position = events.iter().position(|event| {
+            event.method == method
+                && event.uri == uri
+                && stable_headers(&event.headers) == wanted_headers
+                && event.body == body
+        });
+
+        match position {
+            Some(index) => Ok(events.remove(index)),
+            None => Err(ReplayError::NoMatch { method, uri }),
+        }
+    }
+}
+
+impl Service<Request<SdkBody>> for ReplayConnector {
+    type Response = Response<SdkBody>;
+    type Error = ReplayError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<SdkBody>) -> Self::Future {
+        let result = self.take_matching(&req);
+
+        Box::pin(async move {
+            let event = result?;
+            Ok(Response::builder()
+                .status(event.status)
+                .body(SdkBody::from(event.response_body))
+                .expect("recorded event produces a valid response"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn event(body: &str, response_body: &str) -> RecordedEvent {
+        RecordedEvent {
+            method: "POST".to_string(),
+            uri: "/".to_string(),
+            headers: vec![
+                ("content-type".to_string(), "application/x-amz-json-1.0".to_string()),
+                ("authorization".to_string(), "AWS4-HMAC-SHA256 recorded".to_string()),
+                ("x-amz-user-agent".to_string(), "aws-sdk-rust/1.0 recorded".to_string()),
+                ("date".to_string(), "Mon, 01 Jan 2024 00:00:00 GMT".to_string()),
+            ],
+            body: body.to_string(),
+            status: 200,
+            response_body: response_body.to_string(),
+        }
+    }
+
+    fn request(body: &str) -> Request<SdkBody> {
+        Request::builder()
+            .method("POST")
+            .uri("/")
+            .header("content-type", "application/x-amz-json-1.0")
+            .body(SdkBody::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn matches_recorded_events_to_the_same_action_by_body() {
+        let mut connector = ReplayConnector::from_events(vec![
+            event(r#"{"policyStoreId":"a"}"#, r#"{"policy":"a"}"#),
+            event(r#"{"policyStoreId":"b"}"#, r#"{"policy":"b"}"#),
+        ]);
+
+        let response_b = connector
+            .call(request(r#"{"policyStoreId":"b"}"#))
+            .await
+            .unwrap();
+        let body_b = String::from_utf8_lossy(response_b.body().bytes().unwrap()).to_string();
+        assert_eq!(body_b, r#"{"policy":"b"}"#);
+
+        let response_a = connector
+            .call(request(r#"{"policyStoreId":"a"}"#))
+            .await
+            .unwrap();
+        let body_a = String::from_utf8_lossy(response_a.body().bytes().unwrap()).to_string();
+        assert_eq!(body_a, r#"{"policy":"a"}"#);
+    }
+
+    #[tokio::test]
+    async fn matches_despite_differing_volatile_headers() {
+        let mut connector =
+            ReplayConnector::from_events(vec![event(r#"{"policyStoreId":"a"}"#, r#"{"policy":"a"}"#)]);
+
+        // A live SDK user-agent string, signature, and date will never match what was recorded;
+        // the replay must still find the recording by its stable headers and body.
+        let live_request = Request::builder()
+            .method("POST")
+            .uri("/")
+            .header("content-type", "application/x-amz-json-1.0")
+            .header("authorization", "AWS4-HMAC-SHA256 live-signature")
+            .header("x-amz-user-agent", "aws-sdk-rust/2.0 live")
+            .header("date", "Tue, 02 Feb 2026 12:00:00 GMT")
+            .body(SdkBody::from(r#"{"policyStoreId":"a"}"#))
+            .unwrap();
+
+        let response = connector.call(live_request).await.unwrap();
+        let body = String::from_utf8_lossy(response.body().bytes().unwrap()).to_string();
+        assert_eq!(body, r#"{"policy":"a"}"#);
+    }
+}
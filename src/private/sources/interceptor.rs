@@ -0,0 +1,144 @@
+//! Interceptor hooks that let operators stamp outgoing AVP calls with correlation IDs, tenant
+//! identifiers, and custom user-agent/business-metric tags for observability and auditing.
+use std::fmt;
+use std::sync::Arc;
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextMut;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::ConfigBag;
+use http::{HeaderMap, HeaderName, HeaderValue};
+
+/// Runs before an AVP request is signed and sent, with the chance to append entries to the
+/// `x-amz-user-agent` header (for business-metric tagging) and add arbitrary request headers
+/// needed for correlation and auditing.
+///
+/// Modeled on the request-information interceptor pattern: callers register a list of these on
+/// a `Client` at build time and every `Load`/`Read` call runs them just before signing.
+pub trait RequestInterceptor: Send + Sync {
+    /// Mutates the outgoing request's headers immediately before it is signed.
+    fn modify_before_signing(&self, headers: &mut HeaderMap);
+}
+
+/// Adapts a list of [`RequestInterceptor`]s to the AWS SDK's own interceptor mechanism, so they
+/// run for every `Load` and `Read` call made through a `Client` built with them registered.
+#[derive(Clone)]
+pub(crate) struct RequestInterceptors {
+    interceptors: Arc<Vec<Box<dyn RequestInterceptor>>>,
+}
+
+impl RequestInterceptors {
+    /// Wraps `interceptors` for registration on an AVP `Client`'s `Config`.
+    pub(crate) fn new(interceptors: Vec<Box<dyn RequestInterceptor>>) -> Self {
+        Self {
+            interceptors: Arc::new(interceptors),
+        }
+    }
+
+    /// Runs every registered interceptor, in registration order, against `headers`. Factored out
+    /// of [`Intercept::modify_before_signing`] so the header-mutation behavior can be unit tested
+    /// without needing a live SDK orchestrator context.
+    fn apply(&self, headers: &mut HeaderMap) {
+        for interceptor in self.interceptors.iter() {
+            interceptor.modify_before_signing(headers);
+        }
+    }
+}
+
+impl fmt::Debug for RequestInterceptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestInterceptors")
+            .field("count", &self.interceptors.len())
+            .finish()
+    }
+}
+
+impl Intercept for RequestInterceptors {
+    fn name(&self) -> &'static str {
+        "RequestInterceptors"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        // Header names/values that don't round-trip into the `http` crate's stricter
+        // `HeaderName`/`HeaderValue` types are dropped rather than erroring: they're a handful of
+        // internal, already-validated SDK headers, and failing the whole call over one of them
+        // would be worse than losing it. Because the request's headers are rebuilt from the
+        // converted map below, such a header (and any value that is not visible ASCII) is removed
+        // from the outgoing request as well, not only hidden from the interceptors.
+        let mut headers = HeaderMap::new();
+        for (name, value) in context.request().headers() {
+            if let (Ok(name), Ok(value)) =
+                (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value))
+            {
+                headers.append(name, value);
+            }
+        }
+
+        self.apply(&mut headers);
+
+        let request = context.request_mut();
+        request.headers_mut().clear();
+        for (name, value) in headers.iter() {
+            if let Ok(value) = value.to_str() {
+                request.headers_mut().append(name.as_str(), value);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use http::HeaderValue;
+
+    use super::{HeaderMap, RequestInterceptor, RequestInterceptors};
+
+    struct AppendUserAgentTag(&'static str);
+
+    impl RequestInterceptor for AppendUserAgentTag {
+        fn modify_before_signing(&self, headers: &mut HeaderMap) {
+            headers.append("x-amz-user-agent", HeaderValue::from_static(self.0));
+        }
+    }
+
+    struct AddCorrelationId(&'static str);
+
+    impl RequestInterceptor for AddCorrelationId {
+        fn modify_before_signing(&self, headers: &mut HeaderMap) {
+            headers.insert("x-correlation-id", HeaderValue::from_static(self.0));
+        }
+    }
+
+    #[test]
+    fn runs_every_interceptor_in_registration_order() {
+        let interceptors = RequestInterceptors::new(vec![
+            Box::new(AppendUserAgentTag("lib/tenant-a#1.0")),
+            Box::new(AppendUserAgentTag("md/business-metric#42")),
+            Box::new(AddCorrelationId("abc-123")),
+        ]);
+
+        let mut headers = HeaderMap::new();
+        interceptors.apply(&mut headers);
+
+        let user_agent_tags: Vec<&str> = headers
+            .get_all("x-amz-user-agent")
+            .iter()
+            .map(|value| value.to_str().unwrap())
+            .collect();
+        assert_eq!(
+            user_agent_tags,
+            vec!["lib/tenant-a#1.0", "md/business-metric#42"]
+        );
+        assert_eq!(
+            headers.get("x-correlation-id").unwrap(),
+            &HeaderValue::from_static("abc-123")
+        );
+    }
+}
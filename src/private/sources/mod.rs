@@ -1,9 +1,16 @@
 //! Implements the `PolicySetSource` for Amazon Verified Permissions.
+use std::collections::VecDeque;
+
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
 
 pub mod cache;
+pub mod client;
+#[cfg(test)]
+pub mod dvr;
+pub mod interceptor;
 pub mod policy;
-mod retry;
+pub mod retry;
 pub mod schema;
 pub mod template;
 
@@ -11,6 +18,10 @@ pub mod template;
     Retry AVP API calls for a max of 5 seconds
     There is some randomness in the exponential backoff algorithm but this will likely result in
     a maximum of 4-6 retries in the worst case
+
+    `retry::WithAdaptiveRetry` is an available `Load`/`Read` decorator offering a CUBIC-like,
+    rate-limit-aware alternative to this backoff; nothing in this crate wraps calls in it by
+    default, so this constant is still the deadline the default backoff retries within.
 */
 pub static API_RETRY_TIMEOUT_IN_SECONDS: u64 = 5;
 
@@ -38,6 +49,184 @@ pub trait Load {
     async fn load(&self, input: Self::Input) -> Result<Self::Output, Self::Exception>;
 }
 
+/// State threaded through the stream returned by [`LoadPaginated::paginate`]. Lives entirely
+/// inside the stream's future, so dropping the stream mid-page simply drops this state and
+/// issues no further AVP calls.
+struct PageState<Input, Item> {
+    next_input: Option<Input>,
+    buffer: VecDeque<Item>,
+}
+
+/// Extends [`Load`] for AVP list operations (`ListPolicies`, `ListPolicyTemplates`, etc.) that
+/// are paginated via a `next_token`, letting callers process items incrementally instead of
+/// buffering an entire list into memory.
+#[async_trait]
+pub trait LoadPaginated: Load {
+    /// A single item yielded by the paginated stream, e.g. `PolicyItem`.
+    type Item;
+
+    /// Builds the `Input` for the next page from the previous input and the `next_token`
+    /// returned alongside it.
+    fn next_input(previous: &Self::Input, next_token: String) -> Self::Input;
+
+    /// Splits a loaded `Output` page into its items and an optional `next_token` for the
+    /// following page. A `None` token means the page just loaded was the last one.
+    fn page(output: Self::Output) -> (Vec<Self::Item>, Option<String>);
+
+    /// Returns a cancellation-safe stream that yields items one at a time: it issues the first
+    /// list call when the stream is first polled, yields its items, and transparently fetches the next page once the
+    /// buffer drains, as long as a `next_token` was returned. All pagination state lives in the
+    /// stream itself, so dropping it mid-page issues no further AVP calls.
+    fn paginate<'a>(
+        &'a self,
+        input: Self::Input,
+    ) -> BoxStream<'a, Result<Self::Item, Self::Exception>>
+    where
+        Self: Sized + Sync,
+        Self::Input: Clone + Send + Sync + 'a,
+        Self::Item: Send + 'a,
+        Self::Exception: Send + 'a,
+    {
+        let state = PageState {
+            next_input: Some(input),
+            buffer: VecDeque::new(),
+        };
+
+        stream::unfold(state, move |mut state| async move {
+            loop {
+                if let Some(item) = state.buffer.pop_front() {
+                    return Some((Ok(item), state));
+                }
+
+                let current_input = state.next_input.take()?;
+                match self.load(current_input.clone()).await {
+                    Ok(output) => {
+                        let (items, next_token) = Self::page(output);
+                        state.next_input =
+                            next_token.map(|token| Self::next_input(&current_input, token));
+                        state.buffer = items.into();
+                    }
+                    Err(error) => return Some((Err(error), state)),
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// Eagerly drains [`Self::paginate`] into a `Vec`, recovering the previous
+    /// load-everything-at-once behavior for callers that don't need incremental processing.
+    async fn collect(&self, input: Self::Input) -> Result<Vec<Self::Item>, Self::Exception>
+    where
+        Self: Sized + Sync,
+        Self::Input: Clone + Send + Sync,
+        Self::Item: Send,
+        Self::Exception: Send,
+    {
+        let mut items = Vec::new();
+        let mut pages = self.paginate(input);
+        while let Some(item) = pages.next().await {
+            items.push(item?);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod pagination_test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use futures::StreamExt;
+
+    use super::{Load, LoadPaginated};
+
+    #[derive(Clone)]
+    struct ListInput {
+        next_token: Option<String>,
+    }
+
+    struct Page {
+        items: Vec<i32>,
+        next_token: Option<String>,
+    }
+
+    struct PagedLoader {
+        pages: Vec<(Vec<i32>, Option<String>)>,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Load for PagedLoader {
+        type Input = ListInput;
+        type Output = Page;
+        type Exception = ();
+
+        async fn load(&self, input: Self::Input) -> Result<Self::Output, Self::Exception> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let page_index = match &input.next_token {
+                None => 0,
+                Some(token) => token.parse::<usize>().unwrap(),
+            };
+            let (items, next_token) = self.pages[page_index].clone();
+            Ok(Page { items, next_token })
+        }
+    }
+
+    impl LoadPaginated for PagedLoader {
+        type Item = i32;
+
+        fn next_input(_previous: &Self::Input, next_token: String) -> Self::Input {
+            ListInput {
+                next_token: Some(next_token),
+            }
+        }
+
+        fn page(output: Self::Output) -> (Vec<Self::Item>, Option<String>) {
+            (output.items, output.next_token)
+        }
+    }
+
+    fn three_page_loader() -> PagedLoader {
+        PagedLoader {
+            pages: vec![
+                (vec![1, 2], Some("1".to_string())),
+                (vec![3, 4], Some("2".to_string())),
+                (vec![5], None),
+            ],
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    #[tokio::test]
+    async fn collect_fetches_every_page_until_next_token_is_absent() {
+        let loader = three_page_loader();
+
+        let items = loader
+            .collect(ListInput { next_token: None })
+            .await
+            .unwrap();
+
+        assert_eq!(items, vec![1, 2, 3, 4, 5]);
+        assert_eq!(loader.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn dropping_the_stream_mid_page_issues_no_further_load_calls() {
+        let loader = three_page_loader();
+
+        {
+            let mut stream = loader.paginate(ListInput { next_token: None });
+            assert_eq!(stream.next().await, Some(Ok(1)));
+            assert_eq!(stream.next().await, Some(Ok(2)));
+            // Dropped here, mid-page: the second `load` call (for page index "1") must never
+            // be made.
+        }
+
+        assert_eq!(loader.calls.load(Ordering::SeqCst), 1);
+    }
+}
+
 /// `Read` trait for callers to retrieve policy store data from AVP.
 #[async_trait]
 pub trait Read {
@@ -92,9 +281,12 @@ mod test {
     use aws_smithy_client::test_connection::TestConnection;
     use aws_smithy_http::body::SdkBody;
     use aws_types::region::Region;
+    use aws_smithy_async::rt::sleep::AsyncSleep;
     use http::{Request, Response, StatusCode};
     use serde::Serialize;
 
+    use super::interceptor::{RequestInterceptor, RequestInterceptors};
+
     /// A pair of a request and responses for the mock AVP client.
     pub type RequestResponsePair = (Request<SdkBody>, Response<SdkBody>);
 
@@ -109,6 +301,42 @@ mod test {
         Client::from_conf(conf)
     }
 
+    /// Builds a mock AVP client with the provided events, additionally running `interceptors`
+    /// before every request is signed so tests can exercise header-injection behavior the same
+    /// way production callers do. Returns the `TestConnection` alongside the client so callers
+    /// can inspect what was actually sent.
+    pub fn build_client_with_interceptors(
+        events: Vec<RequestResponsePair>,
+        interceptors: Vec<Box<dyn RequestInterceptor>>,
+    ) -> (Client, TestConnection<SdkBody>) {
+        let connection = TestConnection::new(events);
+        let conf = Config::builder()
+            .credentials_provider(Credentials::new("a", "b", Some("c".to_string()), None, "d"))
+            .region(Region::new("us-east-1"))
+            .http_connector(connection.clone())
+            .interceptor(RequestInterceptors::new(interceptors))
+            .build();
+
+        (Client::from_conf(conf), connection)
+    }
+
+    /// Builds a mock AVP client with the provided events and a custom `sleep_impl`, letting tests
+    /// advance a mock clock to deterministically exercise the expiring cache's TTL and the
+    /// adaptive retry limiter's backoff.
+    pub fn build_client_with_sleep(
+        events: Vec<RequestResponsePair>,
+        sleep_impl: impl AsyncSleep + 'static,
+    ) -> Client {
+        let conf = Config::builder()
+            .credentials_provider(Credentials::new("a", "b", Some("c".to_string()), None, "d"))
+            .region(Region::new("us-east-1"))
+            .http_connector(TestConnection::new(events))
+            .sleep_impl(sleep_impl)
+            .build();
+
+        Client::from_conf(conf)
+    }
+
     /// Builds an event from the provided serializable request and response and status code to be
     /// used with a mock AVP client.
     ///
@@ -153,4 +381,60 @@ mod test {
 
         (request, response)
     }
+
+    struct CountingSleep {
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl AsyncSleep for CountingSleep {
+        fn sleep(&self, duration: std::time::Duration) -> aws_smithy_async::rt::sleep::Sleep {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            aws_smithy_async::rt::sleep::TokioSleep::new().sleep(duration)
+        }
+    }
+
+    #[tokio::test]
+    async fn build_client_with_sleep_drives_the_client_with_the_injected_sleep() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let event = build_empty_event(&serde_json::json!({}), StatusCode::OK);
+        let client = build_client_with_sleep(
+            vec![event],
+            CountingSleep {
+                calls: std::sync::Arc::clone(&calls),
+            },
+        );
+
+        let _ = client.list_policy_stores().send().await;
+
+        assert!(
+            calls.load(std::sync::atomic::Ordering::SeqCst) > 0,
+            "expected the client to drive at least one timer through the injected sleep_impl"
+        );
+    }
+
+    #[tokio::test]
+    async fn build_client_with_interceptors_sends_the_injected_header() {
+        struct AddCorrelationId;
+        impl RequestInterceptor for AddCorrelationId {
+            fn modify_before_signing(&self, headers: &mut http::HeaderMap) {
+                headers.insert(
+                    "x-correlation-id",
+                    http::HeaderValue::from_static("test-correlation-id"),
+                );
+            }
+        }
+
+        let event = build_empty_event(&serde_json::json!({}), StatusCode::OK);
+        let (client, connection) =
+            build_client_with_interceptors(vec![event], vec![Box::new(AddCorrelationId)]);
+
+        let _ = client.list_policy_stores().send().await;
+
+        let requests = connection.requests();
+        assert_eq!(
+            requests.last().unwrap().headers().get("x-correlation-id"),
+            Some(&http::HeaderValue::from_static("test-correlation-id")),
+            "expected the interceptor-injected header to reach the transport"
+        );
+    }
 }
@@ -0,0 +1,255 @@
+//! An adaptive, rate-limit-aware retry policy for AVP API calls, available to callers as an
+//! alternative to a fixed exponential backoff. Mirrors TCP CUBIC: the allowed rate grows back
+//! towards its last known-good value and is cut multiplicatively the moment AVP throttles.
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use aws_smithy_types::retry::{ErrorKind, ProvideErrorKind};
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+use super::{Load, Read, API_RETRY_TIMEOUT_IN_SECONDS};
+
+/// Multiplicative decrease applied to the current rate when AVP reports throttling.
+const BETA: f64 = 0.7;
+
+/// Scaling constant for the cubic growth function, matching the default used by TCP CUBIC.
+const CUBIC_C: f64 = 0.4;
+
+/// The lowest rate the limiter will ever fall to, so a sustained outage doesn't stall requests
+/// indefinitely once AVP recovers.
+const MIN_FILL_RATE: f64 = 1.0;
+
+/// A client-side token bucket whose fill rate adapts to AVP throttling using a CUBIC-like
+/// congestion algorithm: it grows back towards the last rate that worked, and is cut
+/// multiplicatively the moment a throttling error is observed.
+pub struct AdaptiveRateLimiter {
+    state: Mutex<State>,
+}
+
+struct State {
+    /// Current allowed rate, in tokens (requests) per second.
+    fill_rate: f64,
+    /// The highest rate observed before the most recent throttling event.
+    last_max_rate: f64,
+    /// When the current growth curve started, i.e. the time of the last decrease.
+    epoch_start: Instant,
+    /// Tokens currently available in the bucket.
+    tokens: f64,
+    /// When `tokens` was last topped up.
+    last_refill: Instant,
+}
+
+impl AdaptiveRateLimiter {
+    /// Creates a rate limiter starting at `initial_rate` tokens/sec.
+    pub fn new(initial_rate: f64) -> Self {
+        let now = Instant::now();
+        Self {
+            state: Mutex::new(State {
+                fill_rate: initial_rate,
+                last_max_rate: initial_rate,
+                epoch_start: now,
+                tokens: initial_rate,
+                last_refill: now,
+            }),
+        }
+    }
+
+    /// Acquires a single token, sleeping first if the bucket is currently empty.
+    ///
+    /// The overall [`API_RETRY_TIMEOUT_IN_SECONDS`] remains the cap on how long a caller should
+    /// keep retrying; this only governs the delay before the *next* attempt.
+    pub async fn acquire(&self) {
+        let delay = {
+            let mut state = self.state.lock().await;
+            state.refill();
+
+            if state.tokens >= 1.0 {
+                state.tokens -= 1.0;
+                None
+            } else {
+                let deficit = 1.0 - state.tokens;
+                let wait = Duration::from_secs_f64(deficit / state.fill_rate.max(MIN_FILL_RATE));
+                state.tokens = 0.0;
+                Some(wait)
+            }
+        };
+
+        if let Some(delay) = delay {
+            sleep(delay.min(Duration::from_secs(API_RETRY_TIMEOUT_IN_SECONDS))).await;
+        }
+    }
+
+    /// Records a successful call, growing the allowed rate back towards `last_max_rate` along a
+    /// cubic curve: `rate = C * (t - K)^3 + last_max_rate`, where `t` is the time since the last
+    /// decrease and `K` is chosen so the curve passes through the current rate at `t = 0`.
+    pub async fn on_success(&self) {
+        let mut state = self.state.lock().await;
+        let now = Instant::now();
+        let t = now.duration_since(state.epoch_start).as_secs_f64();
+
+        let k = ((state.last_max_rate - state.fill_rate) / CUBIC_C).cbrt();
+        let grown = CUBIC_C * (t - k).powi(3) + state.last_max_rate;
+
+        state.fill_rate = grown.max(state.fill_rate);
+    }
+
+    /// Records a throttling error from AVP (e.g. `ThrottlingException`), cutting the rate
+    /// multiplicatively and remembering the pre-throttle rate as the new growth ceiling.
+    pub async fn on_throttled(&self) {
+        let mut state = self.state.lock().await;
+        state.last_max_rate = state.fill_rate;
+        state.fill_rate = (state.fill_rate * BETA).max(MIN_FILL_RATE);
+        state.epoch_start = Instant::now();
+    }
+}
+
+impl State {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.fill_rate).min(self.fill_rate);
+        self.last_refill = now;
+    }
+}
+
+/// Wraps a [`Load`] or [`Read`] implementation so every call first acquires a token from an
+/// [`AdaptiveRateLimiter`], retrying on AVP throttling errors until [`API_RETRY_TIMEOUT_IN_SECONDS`]
+/// elapses. Successes and throttling errors feed back into the limiter so its rate adapts over
+/// time. Calls only go through the limiter when wrapped in this decorator; nothing in this crate
+/// wraps them by default.
+pub struct WithAdaptiveRetry<T> {
+    inner: T,
+    limiter: AdaptiveRateLimiter,
+}
+
+impl<T> WithAdaptiveRetry<T> {
+    /// Wraps `inner`, starting the limiter at `initial_rate` tokens/sec.
+    pub fn new(inner: T, initial_rate: f64) -> Self {
+        Self {
+            inner,
+            limiter: AdaptiveRateLimiter::new(initial_rate),
+        }
+    }
+}
+
+/// Runs `attempt` through `limiter`, retrying on throttling errors until the overall
+/// [`API_RETRY_TIMEOUT_IN_SECONDS`] deadline has passed.
+async fn retry_with_limiter<F, Fut, O, E>(limiter: &AdaptiveRateLimiter, attempt: F) -> Result<O, E>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<O, E>>,
+    E: ProvideErrorKind,
+{
+    let deadline = Instant::now() + Duration::from_secs(API_RETRY_TIMEOUT_IN_SECONDS);
+    loop {
+        limiter.acquire().await;
+        match attempt().await {
+            Ok(output) => {
+                limiter.on_success().await;
+                return Ok(output);
+            }
+            Err(error) => {
+                let is_throttling =
+                    matches!(error.retryable_error_kind(), Some(ErrorKind::ThrottlingError));
+                if is_throttling && Instant::now() < deadline {
+                    limiter.on_throttled().await;
+                    continue;
+                }
+                return Err(error);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<T> Load for WithAdaptiveRetry<T>
+where
+    T: Load + Sync,
+    T::Input: Clone + Send + Sync,
+    T::Output: Send,
+    T::Exception: ProvideErrorKind + Send,
+{
+    type Input = T::Input;
+    type Output = T::Output;
+    type Exception = T::Exception;
+
+    async fn load(&self, input: Self::Input) -> Result<Self::Output, Self::Exception> {
+        retry_with_limiter(&self.limiter, || self.inner.load(input.clone())).await
+    }
+}
+
+#[async_trait]
+impl<T> Read for WithAdaptiveRetry<T>
+where
+    T: Read + Sync,
+    T::Input: Clone + Send + Sync,
+    T::Output: Send,
+    T::Exception: ProvideErrorKind + Send,
+{
+    type Input = T::Input;
+    type Output = T::Output;
+    type Exception = T::Exception;
+
+    async fn read(&self, input: Self::Input) -> Result<Self::Output, Self::Exception> {
+        retry_with_limiter(&self.limiter, || self.inner.read(input.clone())).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use aws_smithy_types::retry::{ErrorKind, ProvideErrorKind};
+
+    use super::{Load, WithAdaptiveRetry};
+
+    #[derive(Debug, PartialEq)]
+    struct Throttled;
+
+    impl ProvideErrorKind for Throttled {
+        fn retryable_error_kind(&self) -> Option<ErrorKind> {
+            Some(ErrorKind::ThrottlingError)
+        }
+
+        fn code(&self) -> Option<&str> {
+            Some("ThrottlingException")
+        }
+    }
+
+    struct FlakyLoader {
+        failures_before_success: usize,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Load for FlakyLoader {
+        type Input = ();
+        type Output = &'static str;
+        type Exception = Throttled;
+
+        async fn load(&self, _input: ()) -> Result<Self::Output, Self::Exception> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failures_before_success {
+                Err(Throttled)
+            } else {
+                Ok("ok")
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_on_throttling_until_success() {
+        let loader = FlakyLoader {
+            failures_before_success: 2,
+            calls: AtomicUsize::new(0),
+        };
+        let retrying = WithAdaptiveRetry::new(loader, 100.0);
+
+        let result = retrying.load(()).await;
+
+        assert_eq!(result, Ok("ok"));
+        assert_eq!(retrying.inner.calls.load(Ordering::SeqCst), 3);
+    }
+}